@@ -0,0 +1,84 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{log_utils, path, version_control};
+
+use anyhow;
+use anyhow::Context;
+
+/// The Git sibling of [`crate::sapling::Repo`], speaking `git` instead of
+/// `sl` but otherwise following the same `System` contract.
+pub struct Repo { root: path::AbsPath }
+
+impl version_control::System for Repo {
+    fn new() -> anyhow::Result<Self> {
+        let output = std::process::Command::new("git")
+            .arg("rev-parse")
+            .arg("--show-toplevel")
+            .output()?;
+        anyhow::ensure!(output.status.success(), "Failed to determine Git root");
+        let root = std::str::from_utf8(&output.stdout)?.trim();
+        Ok(Repo { root: path::AbsPath::try_from(root)? })
+    }
+
+    fn get_head(&self) -> anyhow::Result<String> {
+        let mut cmd = std::process::Command::new("git");
+        cmd.arg("rev-parse").arg("HEAD");
+        let output = cmd.current_dir(&self.root).output()?;
+        log_utils::ensure_output(&format!("{:?}", cmd), &output)?;
+        let head = std::str::from_utf8(&output.stdout)?.trim();
+        Ok(head.to_string())
+    }
+
+    fn get_merge_base_with(&self, merge_base_with: &str) -> anyhow::Result<String> {
+        let mut cmd = std::process::Command::new("git");
+        cmd.arg("merge-base").arg("HEAD").arg(merge_base_with);
+        let output = cmd.current_dir(&self.root).output()?;
+        log_utils::ensure_output(&format!("{:?}", cmd), &output)?;
+        let merge_base = std::str::from_utf8(&output.stdout)?.trim();
+        Ok(merge_base.to_string())
+    }
+
+    fn get_changed_files(&self, relative_to: Option<&str>) -> anyhow::Result<Vec<path::AbsPath>> {
+        let mut cmd = std::process::Command::new("git");
+        cmd.arg("diff").arg("--name-only").arg("--diff-filter=d");
+        cmd.arg(relative_to.unwrap_or("HEAD"));
+        cmd.current_dir(&self.root);
+        let output = cmd.output()?;
+        log_utils::ensure_output(&format!("{:?}", cmd), &output)?;
+
+        let changed_files_str = std::str::from_utf8(&output.stdout)?;
+        let changed_files: HashSet<String> = changed_files_str
+            .split('\n')
+            .map(|line| line.to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        log_utils::log_files("Linting commit diff files: ", &changed_files);
+
+        changed_files
+            .into_iter()
+            // Git reports files relative to the repo root, so prepend it.
+            .map(|f| format!("{}", self.root.join(f).display()))
+            .map(|f| {
+                path::AbsPath::try_from(&f).with_context(|| {
+                    format!("Failed to find file while gathering files to lint: {}", f)
+                })
+            })
+            .collect::<anyhow::Result<_>>()
+    }
+
+    fn get_changed_lines(
+        &self,
+        relative_to: Option<&str>,
+    ) -> anyhow::Result<HashMap<path::AbsPath, HashSet<usize>>> {
+        let mut cmd = std::process::Command::new("git");
+        cmd.arg("diff").arg("--unified=0");
+        cmd.arg(relative_to.unwrap_or("HEAD"));
+        cmd.current_dir(&self.root);
+        let output = cmd.output()?;
+        log_utils::ensure_output(&format!("{:?}", cmd), &output)?;
+
+        let diff_str = std::str::from_utf8(&output.stdout)?;
+        version_control::parse_unified_diff(&self.root, diff_str)
+    }
+}
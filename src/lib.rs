@@ -0,0 +1,334 @@
+pub mod git;
+pub mod lint_config;
+pub mod linter;
+pub mod log_utils;
+pub mod path;
+pub mod render;
+pub mod sapling;
+pub mod version_control;
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{
+    linter::{LintMessage, LintSeverity, Linter},
+    path::AbsPath,
+};
+
+/// Performs first-time setup for linters by running each one's `init_args`.
+pub fn do_init(linters: Vec<Linter>, dry_run: bool) -> Result<i32> {
+    let mut had_error = false;
+    for linter in &linters {
+        let Some(init_commands) = &linter.init_commands else {
+            continue;
+        };
+        let dryrun_arg = if dry_run { "--dry-run" } else { "" };
+        let args: Vec<String> = init_commands
+            .iter()
+            .map(|arg| arg.replace("{{DRYRUN}}", dryrun_arg))
+            .collect();
+
+        log::info!("Initializing linter '{}'", linter.name);
+        let status = std::process::Command::new(&args[0])
+            .args(&args[1..])
+            .status()
+            .with_context(|| format!("Failed to run init command for linter '{}'", linter.name))?;
+        if !status.success() {
+            log::error!("Failed to initialize linter '{}'", linter.name);
+            had_error = true;
+        }
+    }
+    Ok(if had_error { 1 } else { 0 })
+}
+
+/// Gathers the set of files to lint from `--paths-cmd`, or the version
+/// control backend's changed files if that's not set.
+fn gather_paths(paths_cmd: Option<&str>) -> Result<Vec<AbsPath>> {
+    if let Some(paths_cmd) = paths_cmd {
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(paths_cmd)
+            .output()
+            .with_context(|| format!("Failed to run --paths-cmd '{}'", paths_cmd))?;
+        log_utils::ensure_output(&format!("paths-cmd '{}'", paths_cmd), &output)?;
+
+        let stdout = std::str::from_utf8(&output.stdout)?;
+        stdout
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(AbsPath::try_from)
+            .collect()
+    } else {
+        version_control::detect()?.get_changed_files(None)
+    }
+}
+
+/// Upper bound on how many linter subprocesses `run_linters` runs at once.
+const MAX_CONCURRENCY: usize = 8;
+
+/// Runs `linters` concurrently across a bounded pool of worker threads.
+/// With `fail_fast`, a linter error keeps linters not yet started from
+/// running (those already running finish) and is returned directly;
+/// otherwise errors are logged and also reported via the bool.
+fn run_linters(
+    linters: &[Linter],
+    paths: &[AbsPath],
+    fail_fast: bool,
+) -> Result<(Vec<LintMessage>, bool)> {
+    let worker_count = MAX_CONCURRENCY.min(linters.len()).max(1);
+    let next_index = AtomicUsize::new(0);
+    let cancelled = AtomicBool::new(false);
+    let results: Mutex<Vec<Option<Result<Vec<LintMessage>>>>> =
+        Mutex::new((0..linters.len()).map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..worker_count {
+            scope.spawn(|| loop {
+                if cancelled.load(Ordering::SeqCst) {
+                    return;
+                }
+                let index = next_index.fetch_add(1, Ordering::SeqCst);
+                let Some(linter) = linters.get(index) else {
+                    return;
+                };
+
+                let config_dir = linter.config_path.parent();
+                let matched: Vec<AbsPath> = paths
+                    .iter()
+                    .filter(|path| {
+                        let relative = config_dir
+                            .and_then(|dir| path.strip_prefix(dir).ok())
+                            .unwrap_or(path.as_pathbuf().as_path());
+                        linter.matches(&relative.to_string_lossy())
+                    })
+                    .cloned()
+                    .collect();
+                let result = linter.run(&matched);
+                if fail_fast && result.is_err() {
+                    cancelled.store(true, Ordering::SeqCst);
+                }
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    let mut messages = Vec::new();
+    let mut any_failed = false;
+    for (index, result) in results.into_inner().unwrap().into_iter().enumerate() {
+        match result {
+            Some(Ok(found)) => messages.extend(found),
+            Some(Err(err)) if fail_fast => {
+                return Err(err).with_context(|| {
+                    format!(
+                        "Linter '{}' failed; aborting the rest due to --fail-fast",
+                        linters[index].name
+                    )
+                });
+            }
+            Some(Err(err)) => {
+                log::error!("Linter '{}' failed: {:?}", linters[index].name, err);
+                any_failed = true;
+            }
+            // Never started because `--fail-fast` cancelled the run first.
+            None => {}
+        }
+    }
+    Ok((messages, any_failed))
+}
+
+/// Creates a fresh, collision-resistant directory under the system temp dir
+/// for `--stdin`, so two concurrent invocations never share a path.
+fn unique_temp_dir() -> Result<std::path::PathBuf> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    for _ in 0..1000 {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let count = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let candidate = std::env::temp_dir().join(format!(
+            "lintrunner-stdin-{}-{}-{}",
+            std::process::id(),
+            nanos,
+            count
+        ));
+        match std::fs::create_dir(&candidate) {
+            Ok(()) => return Ok(candidate),
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(err) => {
+                return Err(err).context("Failed to create a temp directory for --stdin")
+            }
+        }
+    }
+    bail!("Failed to create a unique temp directory for --stdin after 1000 attempts")
+}
+
+/// Reads a file body from stdin and lints it from a temp directory, which is
+/// removed before returning, under the given virtual filename.
+fn lint_stdin(linters: &[Linter], virtual_filename: &str) -> Result<Vec<LintMessage>> {
+    let mut body = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut body)
+        .context("Failed to read file body from stdin")?;
+
+    let file_name = std::path::Path::new(virtual_filename).file_name().with_context(|| {
+        format!(
+            "--stdin filename '{}' has no file name component",
+            virtual_filename
+        )
+    })?;
+
+    let temp_dir = unique_temp_dir()?;
+    let result = (|| {
+        let temp_path = temp_dir.join(file_name);
+        std::fs::write(&temp_path, &body).with_context(|| {
+            format!("Failed to write stdin buffer to '{}'", temp_path.display())
+        })?;
+        let temp_path = AbsPath::new(temp_path)?;
+
+        let mut messages = Vec::new();
+        for linter in linters.iter().filter(|linter| linter.matches(virtual_filename)) {
+            for mut message in linter.run(std::slice::from_ref(&temp_path))? {
+                // Report findings against the virtual filename the editor
+                // knows about, not the temp path we actually linted.
+                if message.path.as_deref() == Some(temp_path.to_string_lossy().as_ref()) {
+                    message.path = Some(virtual_filename.to_string());
+                }
+                messages.push(message);
+            }
+        }
+        Ok(messages)
+    })();
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    result
+}
+
+/// Runs the given linters, renders their findings, and returns a process
+/// exit code (nonzero on an error-severity message or a linter failure).
+#[allow(clippy::too_many_arguments)]
+pub fn do_lint(
+    linters: &[Linter],
+    paths_cmd: Option<String>,
+    _apply_patches: bool,
+    json: bool,
+    diff_only: bool,
+    fail_fast: bool,
+    stdin: Option<String>,
+    _enable_spinners: bool,
+) -> Result<i32> {
+    let (mut messages, linters_failed) = if let Some(virtual_filename) = &stdin {
+        (lint_stdin(linters, virtual_filename)?, false)
+    } else {
+        let paths = gather_paths(paths_cmd.as_deref())?;
+        run_linters(linters, &paths, fail_fast)?
+    };
+
+    if diff_only {
+        let changed_lines = version_control::detect()?.get_changed_lines(None)?;
+        messages = filter_to_changed_lines(messages, &changed_lines);
+    }
+
+    render::render_messages(&messages, json)?;
+
+    let had_error = linters_failed
+        || messages
+            .iter()
+            .any(|message| matches!(message.severity, LintSeverity::Error));
+    Ok(if had_error { 1 } else { 0 })
+}
+
+/// Drops any `LintMessage` whose line isn't in `changed_lines`, keeping
+/// file-level messages (those with no `line`) and messages with no `path`
+/// unconditionally.
+fn filter_to_changed_lines(
+    messages: Vec<LintMessage>,
+    changed_lines: &HashMap<AbsPath, HashSet<usize>>,
+) -> Vec<LintMessage> {
+    messages
+        .into_iter()
+        .filter(|message| {
+            let (Some(path), Some(line)) = (message.path.as_ref(), message.line) else {
+                return true;
+            };
+            let Ok(abs_path) = AbsPath::try_from(path) else {
+                return true;
+            };
+            changed_lines
+                .get(&abs_path)
+                .map(|lines| lines.contains(&line))
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+/// How often `do_watch` polls the gathered files for changes.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(300);
+/// How long `do_watch` waits after the first observed change before
+/// re-linting, so a burst of saves only triggers one run.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Captures each path's last-modified time, for `do_watch` to diff between polls.
+fn snapshot_mtimes(paths: &[AbsPath]) -> HashMap<AbsPath, SystemTime> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            std::fs::metadata(path.as_pathbuf())
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .map(|modified| (path.clone(), modified))
+        })
+        .collect()
+}
+
+/// Lints once, then re-lints whenever the gathered files change, debounced
+/// so a burst of saves only triggers one run.
+pub fn do_watch(
+    linters: &[Linter],
+    paths_cmd: Option<String>,
+    apply_patches: bool,
+    json: bool,
+    diff_only: bool,
+    fail_fast: bool,
+    enable_spinners: bool,
+) -> Result<i32> {
+    let lint_once = |paths_cmd: Option<String>| {
+        do_lint(
+            linters,
+            paths_cmd,
+            apply_patches,
+            json,
+            diff_only,
+            fail_fast,
+            None,
+            enable_spinners,
+        )
+    };
+
+    lint_once(paths_cmd.clone())?;
+    let mut snapshot = snapshot_mtimes(&gather_paths(paths_cmd.as_deref())?);
+
+    loop {
+        thread::sleep(WATCH_POLL_INTERVAL);
+
+        let next_snapshot = snapshot_mtimes(&gather_paths(paths_cmd.as_deref())?);
+        if next_snapshot == snapshot {
+            continue;
+        }
+
+        // Debounce: let the rest of a burst of changes land before acting.
+        thread::sleep(WATCH_DEBOUNCE);
+        snapshot = snapshot_mtimes(&gather_paths(paths_cmd.as_deref())?);
+
+        log::info!("Changes detected, re-linting...");
+        lint_once(paths_cmd.clone())?;
+    }
+}
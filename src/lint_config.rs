@@ -4,6 +4,7 @@ use crate::{linter::Linter, path::AbsPath};
 use anyhow::{bail, Context, Result};
 use glob::Pattern;
 use log::debug;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -22,6 +23,36 @@ struct LintConfig {
     init_args: Option<Vec<String>>,
     #[serde(default)]
     bypass_matched_file_filter: bool,
+    #[serde(default)]
+    scope: LintScope,
+    /// Coarse tags (e.g. "rust", "js", "docs") a linter belongs to, selected
+    /// with `--type` in addition to the existing name-based `--skip`/`--take`.
+    #[serde(default)]
+    categories: Vec<String>,
+    /// A regex with named capture groups `file`, `line`, `col`, `message` and
+    /// `severity`, used to translate a linter's plain-text stdout/stderr into
+    /// `LintMessage`s when the underlying tool doesn't speak the
+    /// `LintMessage` JSON protocol natively. `line`, `col` and `severity` are
+    /// optional; without `line`/`col` the resulting message is treated as
+    /// file-level, and without `severity` it is an error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_regex: Option<String>,
+}
+
+/// Controls whether a linter's command receives the matched files as
+/// arguments.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum LintScope {
+    /// The command is invoked once, with every matched file as an argument
+    /// (today's behavior).
+    #[default]
+    File,
+    /// The command is invoked exactly once with no file arguments, regardless
+    /// of how many files matched, as long as at least one did. Useful for checks that are
+    /// inherently whole-repo, e.g. a workspace-manifest or dependency-graph
+    /// linter.
+    Project,
 }
 
 /// Given options specified by the user, return a list of linters to run.
@@ -29,6 +60,7 @@ pub fn get_linters_from_config(
     config_path: &AbsPath,
     skipped_linters: Option<HashSet<String>>,
     taken_linters: Option<HashSet<String>>,
+    taken_categories: Option<HashSet<String>>,
 ) -> Result<Vec<Linter>> {
     let lint_runner_config = LintRunnerConfig::new(config_path)?;
     let mut linters = Vec::new();
@@ -39,6 +71,11 @@ pub fn get_linters_from_config(
         } else {
             Vec::new()
         };
+        let output_regex = lint_config
+            .output_regex
+            .as_deref()
+            .map(output_regex_from_str)
+            .transpose()?;
         linters.push(Linter {
             name: lint_config.name,
             include_patterns,
@@ -47,6 +84,9 @@ pub fn get_linters_from_config(
             init_commands: lint_config.init_args,
             config_path: config_path.clone(),
             bypass_matched_file_filter: lint_config.bypass_matched_file_filter,
+            run_once_per_project: lint_config.scope == LintScope::Project,
+            categories: lint_config.categories,
+            output_regex,
         });
     }
     debug!(
@@ -57,19 +97,24 @@ pub fn get_linters_from_config(
     // Apply --take
     if let Some(taken_linters) = taken_linters {
         debug!("Taking linters: {:?}", taken_linters);
-        linters = linters
-            .into_iter()
-            .filter(|linter| taken_linters.contains(&linter.name))
-            .collect();
+        linters.retain(|linter| taken_linters.contains(&linter.name));
     }
 
     // Apply --skip
     if let Some(skipped_linters) = skipped_linters {
         debug!("Skipping linters: {:?}", skipped_linters);
-        linters = linters
-            .into_iter()
-            .filter(|linter| !skipped_linters.contains(&linter.name))
-            .collect();
+        linters.retain(|linter| !skipped_linters.contains(&linter.name));
+    }
+
+    // Apply --type
+    if let Some(taken_categories) = taken_categories {
+        debug!("Taking categories: {:?}", taken_categories);
+        linters.retain(|linter| {
+            linter
+                .categories
+                .iter()
+                .any(|category| taken_categories.contains(category))
+        });
     }
     Ok(linters)
 }
@@ -99,7 +144,25 @@ impl LintRunnerConfig {
     }
 }
 
-fn patterns_from_strs(pattern_strs: &Vec<String>) -> Result<Vec<Pattern>> {
+/// Parses and validates a linter's `output_regex`, ensuring it at least
+/// defines the `file` and `message` capture groups needed to build a
+/// file-level `LintMessage`; `line`, `col` and `severity` are optional.
+fn output_regex_from_str(pattern: &str) -> Result<Regex> {
+    let regex = Regex::new(pattern)
+        .with_context(|| format!("Could not parse output_regex: '{}'", pattern))?;
+    for required_group in ["file", "message"] {
+        if regex.capture_names().flatten().all(|name| name != required_group) {
+            bail!(
+                "output_regex '{}' is missing required named capture group '{}'",
+                pattern,
+                required_group
+            );
+        }
+    }
+    Ok(regex)
+}
+
+fn patterns_from_strs(pattern_strs: &[String]) -> Result<Vec<Pattern>> {
     pattern_strs
         .iter()
         .map(|pattern_str| {
@@ -0,0 +1,202 @@
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use glob::Pattern;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::path::AbsPath;
+
+/// Severity of a reported lint message, per the LintMessage spec.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Advice,
+    Disabled,
+}
+
+/// A single lint finding, as emitted by a linter subprocess (or synthesized
+/// from its plain-text output via `output_regex`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintMessage {
+    pub path: Option<String>,
+    pub line: Option<usize>,
+    pub char: Option<usize>,
+    pub code: String,
+    pub severity: LintSeverity,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// A configured linter: the command to invoke, the files it applies to, and
+/// how to interpret its output.
+pub struct Linter {
+    pub name: String,
+    pub include_patterns: Vec<Pattern>,
+    pub exclude_patterns: Vec<Pattern>,
+    pub commands: Vec<String>,
+    pub init_commands: Option<Vec<String>>,
+    pub config_path: AbsPath,
+    pub bypass_matched_file_filter: bool,
+    pub run_once_per_project: bool,
+    pub categories: Vec<String>,
+    pub output_regex: Option<Regex>,
+}
+
+impl Linter {
+    /// Returns true if `path` (relative to the directory holding the config
+    /// file, normally the repo root) should be linted by this linter.
+    pub fn matches(&self, path: &str) -> bool {
+        self.include_patterns.iter().any(|p| p.matches(path))
+            && !self.exclude_patterns.iter().any(|p| p.matches(path))
+    }
+
+    /// Runs this linter against the matched paths, passed together to a
+    /// single invocation. A `project`-scoped linter is invoked once with no
+    /// path arguments instead.
+    pub fn run(&self, paths: &[AbsPath]) -> Result<Vec<LintMessage>> {
+        if paths.is_empty() && !self.bypass_matched_file_filter {
+            return Ok(Vec::new());
+        }
+
+        let mut cmd = Command::new(&self.commands[0]);
+        cmd.args(&self.commands[1..]);
+        if !self.run_once_per_project {
+            for path in paths {
+                cmd.arg(path.as_pathbuf());
+            }
+        }
+
+        let output = cmd
+            .output()
+            .with_context(|| format!("Failed to run linter '{}'", self.name))?;
+
+        if let Some(output_regex) = &self.output_regex {
+            Ok(self.messages_from_output_regex(output_regex, &output.stdout, &output.stderr))
+        } else {
+            parse_lint_messages(&self.name, &output.stdout)
+        }
+    }
+
+    /// Converts plain-text output into `LintMessage`s via `output_regex`'s
+    /// named capture groups, for linters that don't speak LintMessage JSON.
+    fn messages_from_output_regex(
+        &self,
+        output_regex: &Regex,
+        stdout: &[u8],
+        stderr: &[u8],
+    ) -> Vec<LintMessage> {
+        let stdout = String::from_utf8_lossy(stdout);
+        let stderr = String::from_utf8_lossy(stderr);
+
+        stdout
+            .lines()
+            .chain(stderr.lines())
+            .filter_map(|line| output_regex.captures(line))
+            .map(|caps| LintMessage {
+                path: caps.name("file").map(|m| m.as_str().to_string()),
+                line: caps
+                    .name("line")
+                    .and_then(|m| m.as_str().parse::<usize>().ok()),
+                char: caps
+                    .name("col")
+                    .and_then(|m| m.as_str().parse::<usize>().ok()),
+                code: self.name.clone(),
+                severity: caps
+                    .name("severity")
+                    .map(|m| severity_from_str(m.as_str()))
+                    .unwrap_or(LintSeverity::Error),
+                name: self.name.clone(),
+                description: caps.name("message").map(|m| m.as_str().to_string()),
+            })
+            .collect()
+    }
+}
+
+/// Maps a linter's free-form severity text onto `LintSeverity`, defaulting
+/// to `Error` for anything unrecognized.
+fn severity_from_str(severity: &str) -> LintSeverity {
+    match severity.to_ascii_lowercase().as_str() {
+        "warning" | "warn" => LintSeverity::Warning,
+        "advice" | "note" => LintSeverity::Advice,
+        "disabled" => LintSeverity::Disabled,
+        _ => LintSeverity::Error,
+    }
+}
+
+/// Parses a linter's stdout as newline-delimited `LintMessage` JSON, per the
+/// LintMessage spec. Blank lines are skipped.
+fn parse_lint_messages(linter_name: &str, stdout: &[u8]) -> Result<Vec<LintMessage>> {
+    let stdout = std::str::from_utf8(stdout)
+        .with_context(|| format!("Linter '{}' produced non-UTF8 output", linter_name))?;
+
+    stdout
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).with_context(|| {
+                format!(
+                    "Linter '{}' produced a line that wasn't valid LintMessage JSON: {}",
+                    linter_name, line
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_linter(output_regex: Regex) -> Linter {
+        Linter {
+            name: "mylint".to_string(),
+            include_patterns: Vec::new(),
+            exclude_patterns: Vec::new(),
+            commands: Vec::new(),
+            init_commands: None,
+            config_path: AbsPath::try_from("/tmp").unwrap(),
+            bypass_matched_file_filter: false,
+            run_once_per_project: false,
+            categories: Vec::new(),
+            output_regex: Some(output_regex),
+        }
+    }
+
+    #[test]
+    fn messages_from_output_regex_parses_named_groups() {
+        let regex = Regex::new(
+            r"^(?P<file>\S+):(?P<line>\d+):(?P<col>\d+): (?P<severity>\w+): (?P<message>.*)$",
+        )
+        .unwrap();
+        let linter = test_linter(regex.clone());
+
+        let stdout = b"foo.rs:3:5: warning: unused variable\nnot a match\n";
+        let messages = linter.messages_from_output_regex(&regex, stdout, b"");
+
+        assert_eq!(messages.len(), 1);
+        let message = &messages[0];
+        assert_eq!(message.path.as_deref(), Some("foo.rs"));
+        assert_eq!(message.line, Some(3));
+        assert_eq!(message.char, Some(5));
+        assert_eq!(message.severity, LintSeverity::Warning);
+        assert_eq!(message.description.as_deref(), Some("unused variable"));
+    }
+
+    #[test]
+    fn messages_from_output_regex_without_line_col_is_file_level() {
+        let regex = Regex::new(r"^(?P<file>\S+): (?P<message>.*)$").unwrap();
+        let linter = test_linter(regex.clone());
+
+        let messages = linter.messages_from_output_regex(&regex, b"", b"foo.rs: something broke\n");
+
+        assert_eq!(messages.len(), 1);
+        let message = &messages[0];
+        assert_eq!(message.path.as_deref(), Some("foo.rs"));
+        assert_eq!(message.line, None);
+        assert_eq!(message.char, None);
+        assert_eq!(message.severity, LintSeverity::Error);
+    }
+}
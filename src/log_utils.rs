@@ -0,0 +1,23 @@
+use std::{collections::HashSet, process::Output};
+
+use anyhow::{bail, Result};
+use log::debug;
+
+/// Ensures a subprocess exited successfully, bailing with its stderr
+/// attached if it didn't.
+pub fn ensure_output(cmd_description: &str, output: &Output) -> Result<()> {
+    if !output.status.success() {
+        bail!(
+            "Command failed: {}\nstatus: {}\nstderr:\n{}",
+            cmd_description,
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(())
+}
+
+/// Logs, at debug level, the set of files about to be acted on.
+pub fn log_files(prefix: &str, files: &HashSet<String>) {
+    debug!("{}{:?}", prefix, files);
+}
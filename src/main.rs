@@ -4,7 +4,8 @@ use anyhow::{Context, Result};
 use structopt::StructOpt;
 
 use lintrunner::{
-    do_init, do_lint, lint_config::get_linters_from_config, path::AbsPath, render::print_error,
+    do_init, do_lint, do_watch, lint_config::get_linters_from_config, path::AbsPath,
+    render::print_error,
 };
 
 #[derive(Debug, StructOpt)]
@@ -34,11 +35,34 @@ struct Opt {
     #[structopt(long)]
     take: Option<String>,
 
+    /// Comma-separated list of linter categories to run (e.g. --type rust,docs)
+    #[structopt(long = "type")]
+    type_: Option<String>,
+
     /// If set, lintrunner will render lint messages as JSON, according to the
     /// LintMessage spec.
     #[structopt(long)]
     json: bool,
 
+    /// If set, only report lint messages that fall on lines added or
+    /// modified in the current commit range, as determined by parsing the
+    /// version control backend's diff output.
+    #[structopt(long)]
+    diff_only: bool,
+
+    /// If set, the first linter that fails keeps linters not yet started from
+    /// running, and lintrunner exits with an error once the linters already
+    /// running have finished. Linters run concurrently either way.
+    #[structopt(long)]
+    fail_fast: bool,
+
+    /// Lint file contents read from stdin instead of the working tree, using
+    /// the given virtual filename to select linters and match patterns
+    /// (e.g. --stdin foo.py). Intended for editor/LSP integrations linting
+    /// an unsaved buffer.
+    #[structopt(long)]
+    stdin: Option<String>,
+
     #[structopt(subcommand)]
     cmd: Option<SubCommand>,
 }
@@ -51,6 +75,8 @@ enum SubCommand {
         #[structopt(long, short)]
         dry_run: bool,
     },
+    /// Watch the files that would be linted and re-lint whenever one changes
+    Watch,
 }
 
 fn do_main() -> Result<i32> {
@@ -80,8 +106,19 @@ fn do_main() -> Result<i32> {
             .map(|linter_name| linter_name.to_string())
             .collect::<HashSet<_>>()
     });
+    let taken_categories = opt.type_.map(|categories| {
+        categories
+            .split(',')
+            .map(|category| category.to_string())
+            .collect::<HashSet<_>>()
+    });
 
-    let linters = get_linters_from_config(&config_path, skipped_linters, taken_linters)?;
+    let linters = get_linters_from_config(
+        &config_path,
+        skipped_linters,
+        taken_linters,
+        taken_categories,
+    )?;
 
     let enable_spinners = !opt.verbose && !opt.json;
 
@@ -90,13 +127,32 @@ fn do_main() -> Result<i32> {
             // Just run initialization commands, don't actually lint.
             do_init(linters, dry_run)
         }
+        Some(SubCommand::Watch) => {
+            anyhow::ensure!(
+                opt.stdin.is_none(),
+                "--stdin lints a single buffer snapshot and cannot be combined with watch"
+            );
+            // Lint once, then keep re-linting as the gathered files change.
+            do_watch(
+                &linters,
+                opt.paths_cmd,
+                opt.apply_patches,
+                opt.json,
+                opt.diff_only,
+                opt.fail_fast,
+                enable_spinners,
+            )
+        }
         None => {
             // Default command is to just lint.
             do_lint(
-                linters,
+                &linters,
                 opt.paths_cmd,
                 opt.apply_patches,
                 opt.json,
+                opt.diff_only,
+                opt.fail_fast,
+                opt.stdin,
                 enable_spinners,
             )
         }
@@ -0,0 +1,71 @@
+use std::{
+    convert::TryFrom,
+    fmt,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+/// A path that is guaranteed to be absolute.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AbsPath(PathBuf);
+
+impl AbsPath {
+    pub fn new(path: PathBuf) -> Result<Self> {
+        let abs = if path.is_absolute() {
+            path
+        } else {
+            std::env::current_dir()
+                .context("Could not determine current directory")?
+                .join(path)
+        };
+        // Canonicalize when possible, but fall back to the joined path for
+        // ones that don't exist yet (e.g. a --stdin virtual filename).
+        Ok(AbsPath(abs.canonicalize().unwrap_or(abs)))
+    }
+
+    pub fn as_pathbuf(&self) -> &PathBuf {
+        &self.0
+    }
+
+    pub fn join<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.0.join(path)
+    }
+}
+
+impl TryFrom<&str> for AbsPath {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self> {
+        AbsPath::new(PathBuf::from(value))
+    }
+}
+
+impl TryFrom<&String> for AbsPath {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &String) -> Result<Self> {
+        AbsPath::new(PathBuf::from(value))
+    }
+}
+
+impl Deref for AbsPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for AbsPath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl fmt::Display for AbsPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.display())
+    }
+}
@@ -0,0 +1,46 @@
+use anyhow::Result;
+
+use crate::linter::{LintMessage, LintSeverity};
+
+/// Prints a top-level failure (as opposed to a lint finding) to stderr.
+pub fn print_error(err: &anyhow::Error) -> Result<()> {
+    eprintln!("lintrunner error: {:?}", err);
+    Ok(())
+}
+
+/// Renders collected lint messages, either as newline-delimited JSON
+/// (following the LintMessage spec) or as human-readable text.
+pub fn render_messages(messages: &[LintMessage], json: bool) -> Result<()> {
+    if json {
+        for message in messages {
+            println!("{}", serde_json::to_string(message)?);
+        }
+        return Ok(());
+    }
+
+    for message in messages {
+        let location = match (&message.path, message.line) {
+            (Some(path), Some(line)) => format!("{}:{}", path, line),
+            (Some(path), None) => path.clone(),
+            _ => "<unknown>".to_string(),
+        };
+        println!(
+            "{} [{}] {}: {}",
+            severity_label(message.severity),
+            message.code,
+            location,
+            message.description.as_deref().unwrap_or(&message.name),
+        );
+    }
+
+    Ok(())
+}
+
+fn severity_label(severity: LintSeverity) -> &'static str {
+    match severity {
+        LintSeverity::Error => "Error",
+        LintSeverity::Warning => "Warning",
+        LintSeverity::Advice => "Advice",
+        LintSeverity::Disabled => "Disabled",
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::{HashMap, HashSet};
+
 use crate::{log_utils, path, version_control};
 
 use anyhow;
@@ -81,4 +83,21 @@ impl version_control::System for Repo {
             })
             .collect::<anyhow::Result<_>>()
     }
+
+    fn get_changed_lines(
+        &self,
+        relative_to: Option<&str>,
+    ) -> anyhow::Result<HashMap<path::AbsPath, HashSet<usize>>> {
+        let mut cmd = std::process::Command::new("sl");
+        cmd.arg("diff").arg("--unified=0");
+        if let Some(relative_to) = relative_to {
+            cmd.arg(format!("--rev={}", relative_to));
+        }
+        cmd.current_dir(&self.root);
+        let output = cmd.output()?;
+        log_utils::ensure_output(&format!("{:?}", cmd), &output)?;
+
+        let diff_str = std::str::from_utf8(&output.stdout)?;
+        version_control::parse_unified_diff(&self.root, diff_str)
+    }
 }
@@ -0,0 +1,131 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+
+use crate::path::AbsPath;
+
+/// Abstraction over the version control backend (Sapling or Git) lintrunner
+/// is run against.
+pub trait System {
+    fn new() -> Result<Self>
+    where
+        Self: Sized;
+
+    fn get_head(&self) -> Result<String>;
+
+    fn get_merge_base_with(&self, merge_base_with: &str) -> Result<String>;
+
+    fn get_changed_files(&self, relative_to: Option<&str>) -> Result<Vec<AbsPath>>;
+
+    /// New-side line numbers touched by the diff, keyed by file. Backs `--diff-only`.
+    fn get_changed_lines(
+        &self,
+        relative_to: Option<&str>,
+    ) -> Result<HashMap<AbsPath, HashSet<usize>>>;
+}
+
+/// Parses unified diff text (as produced by `sl diff`/`git diff`) into a map
+/// of new-side line numbers touched, per file. Shared by every `System`
+/// backend, since they all emit the same hunk format.
+pub fn parse_unified_diff(
+    root: &AbsPath,
+    diff_str: &str,
+) -> Result<HashMap<AbsPath, HashSet<usize>>> {
+    let file_header = regex::Regex::new(r"^\+\+\+ b/(.+)$")?;
+    let hunk_header = regex::Regex::new(r"^@@ -\d+(?:,\d+)? \+(\d+)(?:,\d+)? @@")?;
+
+    let mut changed_lines: HashMap<AbsPath, HashSet<usize>> = HashMap::new();
+    let mut current_file: Option<AbsPath> = None;
+    let mut current_line: usize = 0;
+
+    for line in diff_str.lines() {
+        if let Some(caps) = file_header.captures(line) {
+            let abs = format!("{}", root.join(&caps[1]).display());
+            current_file = AbsPath::try_from(&abs).ok();
+            continue;
+        }
+        let Some(current_file) = &current_file else {
+            continue;
+        };
+        if let Some(caps) = hunk_header.captures(line) {
+            current_line = caps[1].parse()?;
+            continue;
+        }
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        if line.starts_with('+') {
+            changed_lines
+                .entry(current_file.clone())
+                .or_default()
+                .insert(current_line);
+            current_line += 1;
+        } else if line.starts_with(' ') {
+            // Context line: present on both sides, advance the new-side
+            // counter but don't mark it as changed.
+            current_line += 1;
+        }
+        // '-' lines only exist on the old side, so they don't advance
+        // `current_line`.
+    }
+
+    Ok(changed_lines)
+}
+
+/// Detects which version control backend the current directory is checked
+/// out with, probing Sapling first and falling back to Git.
+pub fn detect() -> Result<Box<dyn System>> {
+    if let Ok(repo) = crate::sapling::Repo::new() {
+        return Ok(Box::new(repo));
+    }
+    Ok(Box::new(crate::git::Repo::new()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root() -> AbsPath {
+        AbsPath::try_from("/tmp").unwrap()
+    }
+
+    #[test]
+    fn multi_hunk_diff_collects_added_lines_from_every_hunk() {
+        let diff = "\
+diff --git a/foo.rs b/foo.rs
+--- a/foo.rs
++++ b/foo.rs
+@@ -1,2 +1,3 @@
+ fn foo() {
++    println!(\"a\");
+ }
+@@ -10,1 +11,2 @@
+ fn bar() {
++    println!(\"b\");
+";
+        let changed = parse_unified_diff(&root(), diff).unwrap();
+        let foo = root().join("foo.rs");
+        let path = AbsPath::try_from(&format!("{}", foo.display())).unwrap();
+        assert_eq!(
+            changed.get(&path).unwrap(),
+            &HashSet::from([2, 12])
+        );
+    }
+
+    #[test]
+    fn deletion_only_hunk_adds_no_changed_lines() {
+        let diff = "\
+diff --git a/foo.rs b/foo.rs
+--- a/foo.rs
++++ b/foo.rs
+@@ -1,3 +1,2 @@
+ fn foo() {
+-    println!(\"dead code\");
+ }
+";
+        let changed = parse_unified_diff(&root(), diff).unwrap();
+        let foo = root().join("foo.rs");
+        let path = AbsPath::try_from(&format!("{}", foo.display())).unwrap();
+        assert!(!changed.contains_key(&path));
+    }
+}